@@ -0,0 +1,69 @@
+//! Opcode-hit coverage tracking across a parallel test-suite run: which
+//! opcodes were exercised (from the geth trace `struct_logs`) and, by
+//! complement, which were never hit by any test. This is a proxy for circuit
+//! coverage, since every opcode corresponds to gates/lookups in the EVM
+//! circuit that stay unexercised until something hits them.
+use anyhow::Result;
+use eth_types::evm_types::OpcodeId;
+use prettytable::Table;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Shared, lock-protected opcode-hit counters, safe to update from the
+/// `into_par_iter` closure in [`crate::run_test_suite`].
+#[derive(Default)]
+pub struct Coverage {
+    hits: RwLock<HashMap<OpcodeId, u64>>,
+}
+
+impl Coverage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one hit per opcode yielded by `ops` (typically
+    /// `trace.struct_logs.iter().map(|step| step.op)`).
+    pub fn record(&self, ops: impl Iterator<Item = OpcodeId>) {
+        let mut hits = self.hits.write().unwrap();
+        for op in ops {
+            *hits.entry(op).or_insert(0) += 1;
+        }
+    }
+
+    /// Writes the raw hit counts to `path` as JSON, keyed by opcode name.
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        let hits = self.hits.read().unwrap();
+        let by_name: HashMap<String, u64> = hits
+            .iter()
+            .map(|(op, count)| (format!("{:?}", op), *count))
+            .collect();
+        std::fs::write(path, serde_json::to_string_pretty(&by_name)?)?;
+        Ok(())
+    }
+
+    /// Prints a hit-count table plus the list of opcodes never hit, out of
+    /// every single-byte opcode value `0x00..=0xff`.
+    pub fn print_summary(&self) {
+        let hits = self.hits.read().unwrap();
+
+        let mut table = Table::new();
+        table.add_row(row!["OPCODE", "HITS"]);
+        let mut by_count: Vec<_> = hits.iter().collect();
+        by_count.sort_by(|a, b| b.1.cmp(a.1));
+        for (op, count) in &by_count {
+            table.add_row(row![format!("{:?}", op), count]);
+        }
+        table.printstd();
+
+        let unexercised: Vec<OpcodeId> = (0u8..=0xffu8)
+            .map(OpcodeId::from)
+            .filter(|op| !hits.contains_key(op))
+            .collect();
+        println!(
+            "{} opcode values never exercised: {:?}",
+            unexercised.len(),
+            unexercised
+        );
+    }
+}
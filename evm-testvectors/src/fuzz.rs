@@ -0,0 +1,258 @@
+//! Differential fuzzing: generate random bytecode, run it through both the
+//! circuit (`run_test_circuits`, same path as `run_bytecode`) and a geth
+//! trace, and flag any divergence. Turns the tool from a fixed-vector runner
+//! into a bug-finding harness.
+use eth_types::bytecode::Bytecode;
+use eth_types::evm_types::OpcodeId;
+use eth_types::Word;
+use mock::TestContext;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::path::PathBuf;
+use zkevm_circuits::test_util::{run_test_circuits, BytecodeTestConfig};
+
+/// Ops favored by the generator: stack-balanced PUSH/arithmetic/memory,
+/// which are far more likely to produce a runnable (non-immediately-reverted)
+/// sequence than a uniform sample over every opcode.
+const BIASED_OPS: &[OpcodeId] = &[
+    OpcodeId::PUSH1,
+    OpcodeId::ADD,
+    OpcodeId::SUB,
+    OpcodeId::MUL,
+    OpcodeId::DIV,
+    OpcodeId::MOD,
+    OpcodeId::AND,
+    OpcodeId::OR,
+    OpcodeId::XOR,
+    OpcodeId::MLOAD,
+    OpcodeId::MSTORE,
+    OpcodeId::POP,
+    OpcodeId::DUP1,
+    OpcodeId::SWAP1,
+];
+
+/// Generates a random, stack-balanced-biased bytecode sequence of up to
+/// `max_ops` instructions, followed by a `STOP`.
+pub fn generate_bytecode(rng: &mut impl Rng, max_ops: usize) -> Bytecode {
+    let mut bytecode = Bytecode::default();
+    let ops = rng.gen_range(1..=max_ops);
+    for _ in 0..ops {
+        let op = BIASED_OPS[rng.gen_range(0..BIASED_OPS.len())];
+        if op == OpcodeId::PUSH1 {
+            bytecode.push(1, Word::from(rng.gen::<u8>()));
+        } else {
+            bytecode.write_op(op);
+        }
+    }
+    bytecode.write_op(OpcodeId::STOP);
+    bytecode
+}
+
+/// Deterministically derives a seed from a libFuzzer-style input buffer, so
+/// this can be driven by `cargo fuzz` as well as the standalone `fuzz`
+/// subcommand.
+pub fn bytecode_from_fuzz_bytes(data: &[u8]) -> Bytecode {
+    let mut seed_bytes = [0u8; 8];
+    for (i, byte) in data.iter().take(8).enumerate() {
+        seed_bytes[i] = *byte;
+    }
+    let mut rng = StdRng::seed_from_u64(u64::from_le_bytes(seed_bytes));
+    generate_bytecode(&mut rng, 32.max(data.len()))
+}
+
+/// A confirmed divergence between the circuit and the geth trace reference.
+pub struct FuzzMismatch {
+    pub bytecode_hex: String,
+    /// Which oracle disagreed and how.
+    pub kind: MismatchKind,
+    /// Gas geth reported for the trace of the bytecode the mismatch was
+    /// first found on. `shrink` matches reductions on `kind` only, so after
+    /// shrinking this is still the original bytecode's gas, not the
+    /// reduced one's. `run_test_circuits` only ever
+    /// returns pass/fail — it doesn't surface the circuit's own gas usage
+    /// or final state — so there is no circuit-side gas/state to compare
+    /// this against; see `MismatchKind` for what can actually be detected
+    /// through that API.
+    pub geth_gas: u64,
+}
+
+impl FuzzMismatch {
+    fn reason(&self) -> String {
+        format!("{} (geth gas={})", self.kind, self.geth_gas)
+    }
+}
+
+/// The specific way the circuit and geth disagreed about a bytecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MismatchKind {
+    /// Circuit accepted a trace geth marked as failed.
+    AcceptedFailedTrace,
+    /// Circuit rejected a trace geth marked as successful.
+    RejectedSuccessfulTrace,
+    /// The harness panicked while comparing the two oracles.
+    Panicked,
+}
+
+impl std::fmt::Display for MismatchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            MismatchKind::AcceptedFailedTrace => "circuit accepted a trace geth marked as failed",
+            MismatchKind::RejectedSuccessfulTrace => {
+                "circuit rejected a trace geth marked as successful"
+            }
+            MismatchKind::Panicked => "panicked",
+        };
+        f.write_str(msg)
+    }
+}
+
+const MISMATCH_DIR: &str = "fuzz_failures";
+
+impl FuzzMismatch {
+    /// Persists the failing bytecode as a hex file under `fuzz_failures/`,
+    /// replayable with `--raw <hex>`.
+    fn persist(&self) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(MISMATCH_DIR)?;
+        let path = PathBuf::from(MISMATCH_DIR).join(format!("{}.hex", &self.bytecode_hex[..16.min(self.bytecode_hex.len())]));
+        std::fs::write(&path, &self.bytecode_hex)?;
+        Ok(path)
+    }
+}
+
+/// Runs `bytecode` through both the circuit and the geth trace reference,
+/// comparing geth's `failed` flag against the circuit's pass/fail result,
+/// and returns `Some` on divergence. Gas is recorded, not compared.
+fn compare_bytecode(
+    bytecode: &Bytecode,
+    bytecode_test_config: BytecodeTestConfig,
+) -> anyhow::Result<Option<FuzzMismatch>> {
+    let bytecode_hex = hex::encode(bytecode.code());
+
+    let ctx = TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode.clone())?;
+    let geth_traces = ctx.geth_traces()?;
+    let geth_failed = geth_traces.first().map(|t| t.failed).unwrap_or(false);
+    let geth_gas = geth_traces.first().map(|t| t.gas.0).unwrap_or(0);
+
+    let circuit_result = run_test_circuits(
+        TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode.clone())?,
+        Some(bytecode_test_config),
+    );
+
+    let kind = match (&circuit_result, geth_failed) {
+        (Ok(_), true) => Some(MismatchKind::AcceptedFailedTrace),
+        (Err(_), false) => Some(MismatchKind::RejectedSuccessfulTrace),
+        _ => None,
+    };
+
+    Ok(kind.map(|kind| FuzzMismatch {
+        bytecode_hex,
+        kind,
+        geth_gas,
+    }))
+}
+
+/// Runs the oracle on `bytecode`, treating a panic as a `Panicked` mismatch
+/// rather than aborting the whole fuzz run.
+fn compare_bytecode_catching_panics(
+    bytecode: &Bytecode,
+    bytecode_test_config: BytecodeTestConfig,
+) -> anyhow::Result<Option<FuzzMismatch>> {
+    std::panic::set_hook(Box::new(|_info| {}));
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        compare_bytecode(bytecode, bytecode_test_config)
+    })) {
+        Ok(result) => result,
+        Err(_) => Ok(Some(FuzzMismatch {
+            bytecode_hex: hex::encode(bytecode.code()),
+            kind: MismatchKind::Panicked,
+            geth_gas: 0,
+        })),
+    }
+}
+
+/// Greedily drops trailing bytes (in halving, then unit, steps — a simple
+/// ddmin variant) from `bytecode`, keeping each reduction only if it still
+/// reproduces the *same kind* of mismatch, so the persisted failure is the
+/// smallest sequence that still demonstrates the bug rather than the raw
+/// random input that happened to trigger it.
+fn shrink(
+    bytecode: &Bytecode,
+    bytecode_test_config: BytecodeTestConfig,
+    target_kind: MismatchKind,
+) -> Bytecode {
+    let mut code = bytecode.code();
+
+    let still_reproduces = |code: &[u8]| -> bool {
+        let candidate = match Bytecode::try_from(code.to_vec()) {
+            Ok(b) => b,
+            Err(_) => return false,
+        };
+        matches!(
+            compare_bytecode_catching_panics(&candidate, bytecode_test_config.clone()),
+            Ok(Some(m)) if m.kind == target_kind
+        )
+    };
+
+    let mut step = code.len() / 2;
+    while step > 0 {
+        let mut progress = true;
+        while progress {
+            progress = false;
+            if code.len() <= step {
+                break;
+            }
+            let candidate = &code[..code.len() - step];
+            if still_reproduces(candidate) {
+                code = candidate.to_vec();
+                progress = true;
+            }
+        }
+        step /= 2;
+    }
+
+    Bytecode::try_from(code).unwrap_or_else(|_| bytecode.clone())
+}
+
+/// Runs `iterations` random bytecodes through the differential oracle,
+/// shrinking and persisting any mismatch and logging its replay path.
+pub fn run_fuzz(
+    iterations: u64,
+    seed: u64,
+    bytecode_test_config: BytecodeTestConfig,
+) -> anyhow::Result<()> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut found = 0u64;
+
+    for i in 0..iterations {
+        let bytecode = generate_bytecode(&mut rng, 32);
+
+        let mismatch =
+            match compare_bytecode_catching_panics(&bytecode, bytecode_test_config.clone()) {
+                Ok(mismatch) => mismatch,
+                Err(err) => {
+                    log::warn!("fuzz iteration {} errored: {:?}", i, err);
+                    continue;
+                }
+            };
+
+        if let Some(mismatch) = mismatch {
+            let minimal = shrink(&bytecode, bytecode_test_config.clone(), mismatch.kind);
+            let minimal_mismatch = FuzzMismatch {
+                bytecode_hex: hex::encode(minimal.code()),
+                ..mismatch
+            };
+
+            let path = minimal_mismatch.persist()?;
+            found += 1;
+            log::error!(
+                "fuzz: mismatch at iteration {}: {} (minimal failing sequence, replay with --raw $(cat {}))",
+                i,
+                minimal_mismatch.reason(),
+                path.display()
+            );
+        }
+    }
+
+    log::info!("fuzz: {} iterations, {} mismatches found", iterations, found);
+    Ok(())
+}
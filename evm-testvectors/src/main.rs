@@ -1,12 +1,20 @@
 mod abi;
 mod code_cache;
+mod coverage;
+mod dump_state;
+mod fuzz;
+mod json;
 mod lllc;
 mod result_cache;
+mod skip;
 mod statetest;
 mod utils;
 mod yaml;
 
+use crate::coverage::Coverage;
+use crate::json::JsonStateTestBuilder;
 use crate::lllc::Lllc;
+use crate::skip::{SkipCategory, SkipManifest};
 use crate::yaml::YamlStateTestBuilder;
 use anyhow::{bail, Result};
 use clap::Parser;
@@ -25,6 +33,15 @@ use crate::utils::config_bytecode_test_config;
 extern crate prettytable;
 use prettytable::Table;
 
+/// Source format of the test files being loaded.
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq, Eq)]
+enum TestFormat {
+    /// `*Filler.yml` sources, compiled via LLLC/Docker.
+    Yaml,
+    /// Already-compiled `GeneralStateTests` JSON, no compile step needed.
+    Json,
+}
+
 /// EVM test vectors utility
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -37,6 +54,10 @@ struct Args {
     )]
     path: String,
 
+    /// Format of the test files at `path`
+    #[clap(long, value_enum, default_value = "yaml")]
+    format: TestFormat,
+
     /// Test to execute
     #[clap(short, long)]
     test: Option<String>,
@@ -52,15 +73,43 @@ struct Args {
     /// Raw execute bytecode
     #[clap(short, long)]
     raw: Option<String>,
+
+    /// Only run the given fork's post-state expectations (e.g. "Istanbul").
+    /// Unset runs every fork each test defines.
+    #[clap(long)]
+    fork: Option<String>,
+
+    /// Track opcode-hit coverage across the run and emit coverage.json
+    #[clap(long)]
+    coverage: bool,
+
+    /// Structured skip/expected-failure manifest, see skip.rs
+    #[clap(long, default_value = "skip.toml")]
+    skip_manifest: String,
+
+    /// When a single test run with `--test` fails, print a
+    /// per-account/per-slot diff against the expected post-state
+    #[clap(long)]
+    dump_state: bool,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
 }
 
-const TEST_IGNORE_LIST : [&str;1] = ["gasCostMemory_d61_g0_v0"];
-const FILE_IGNORE_LIST : [&str;4]=  [
-        "EIP1559",
-        "EIP2930",
-        "stExample",
-        "ValueOverflowFiller", // weird 0x:biginteger 0x...
-    ];
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Differential-fuzz random bytecode against the circuit and geth.
+    Fuzz {
+        /// Number of random bytecodes to try
+        #[clap(long, default_value_t = 1000)]
+        iterations: u64,
+        /// PRNG seed, for reproducible runs
+        #[clap(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+const COVERAGE_FILE: &str = "coverage.json";
 
 
 
@@ -69,27 +118,96 @@ const FILE_IGNORE_LIST : [&str;4]=  [
 
 const RESULT_CACHE: &str = "result.cache";
 
-fn run_test_suite(tcs: Vec<StateTest>, config: StateTestConfig) -> Result<()> {
+/// `ResultCache` key for a test run: the bare test id would
+/// collide across forks now that one `StateTest` fans out per fork present
+/// in its `post` section, so every cache lookup goes through this instead.
+///
+/// NOTE: `tc.fork` only ever selects *which post-state entry* a run is
+/// checked against; `StateTest::run` still executes every fork's test
+/// against one fixed EVM spec/ruleset rather than the fork-appropriate one.
+/// Wiring real fork -> spec selection in belongs in `StateTestConfig`/
+/// `StateTest::run` themselves — `statetest.rs` isn't present in this
+/// checkout (only declared via `mod statetest;`; confirmed absent from disk),
+/// so that part of the fix can't be made here. `run_test_suite` logs a
+/// warning when a suite spans more than one fork so this isn't silently
+/// mistaken for fork-differentiated results.
+fn cache_key(tc: &StateTest) -> String {
+    format!("{}#{}", tc.id, tc.fork)
+}
+
+fn run_test_suite(
+    tcs: Vec<StateTest>,
+    config: StateTestConfig,
+    fork: Option<&str>,
+    coverage: Option<&Coverage>,
+    skip_manifest: &SkipManifest,
+) -> Result<()> {
     let results = ResultCache::new(PathBuf::from(RESULT_CACHE))?;
 
     let tcs: Vec<StateTest> = tcs
         .into_iter()
-        .filter(|t| !results.contains(&t.id))
+        // Root-only fixtures (`t.fork == json::ROOT_ONLY_FORK`) have no
+        // per-fork `post` section to select among, so `--fork` never
+        // excludes them — only tests that name a real, non-matching fork
+        // are filtered out.
+        .filter(|t| fork.map_or(true, |f| t.fork == f || t.fork == json::ROOT_ONLY_FORK))
+        .filter(|t| !results.contains(&cache_key(t)))
         .collect();
 
+    // `StateTest::run` executes every test against one fixed EVM spec
+    // regardless of `tc.fork` (see `cache_key`'s doc comment) — so a suite
+    // spanning more than one fork doesn't actually get fork-differentiated
+    // results. Surface that loudly rather than let a Frontier-ruleset pass
+    // or failure on a Shanghai-only post entry look like a real signal.
+    let distinct_forks: std::collections::BTreeSet<&str> = tcs
+        .iter()
+        .map(|t| t.fork.as_str())
+        .filter(|f| *f != json::ROOT_ONLY_FORK)
+        .collect();
+    if distinct_forks.len() > 1 {
+        log::warn!(
+            target: "vmvectests",
+            "running {} forks {:?} in one suite, but every test executes against a single fixed EVM spec \
+             (fork-appropriate spec selection isn't wired into StateTestConfig/StateTest::run) — \
+             pass/fail results are not fork-differentiated",
+            distinct_forks.len(),
+            distinct_forks,
+        );
+    }
+
     let results = Arc::new(RwLock::from(results));
 
 
     // for each test
     tcs.into_par_iter().for_each(|tc| {
-        let id = tc.id.clone();
-        if TEST_IGNORE_LIST.contains(&id.as_str()) {
-            return;
-        }
+        let id = cache_key(&tc);
         if results.read().unwrap().contains(&id.as_str()) {
             return;
         }
 
+        let skip = skip_manifest.lookup(&tc.id);
+        if let Some(entry) = skip {
+            if entry.category != SkipCategory::ExpectedFail {
+                log::warn!(
+                    target: "vmvectests",
+                    "SKIPPED test {} : {:?} ({})",
+                    id, entry.category, entry.reason
+                );
+                results
+                    .write()
+                    .unwrap()
+                    .insert(&id, &format!("{:?}: {}", entry.category, entry.reason))
+                    .unwrap();
+                return;
+            }
+        }
+
+        if let Some(coverage) = coverage {
+            if let Ok(trace) = tc.clone().geth_trace() {
+                coverage.record(trace.struct_logs.iter().map(|step| step.op));
+            }
+        }
+
         log::info!("Running {}",id);
         std::panic::set_hook(Box::new(|_info| {}));
         let result = std::panic::catch_unwind(|| tc.run(config.clone()));
@@ -103,6 +221,26 @@ fn run_test_suite(tcs: Vec<StateTest>, config: StateTestConfig) -> Result<()> {
             }
         };
 
+        // an `ExpectedFail` entry inverts pass/fail: a failure is the
+        // expected outcome, and an unexpected pass is the one worth
+        // surfacing as an error so it doesn't rot on the manifest.
+        if skip.is_some() {
+            match result {
+                Ok(()) => {
+                    log::error!(
+                        target: "vmvectests",
+                        "UNEXPECTED PASS for test {} marked ExpectedFail",
+                        id
+                    );
+                }
+                Err(err) => {
+                    log::info!(target: "vmvectests", "expected failure for test {} : {:?}", id, err);
+                    results.write().unwrap().insert(&id, "expected failure").unwrap();
+                }
+            }
+            return;
+        }
+
         // handle known error
         if let Err(err) = result {
             match err {
@@ -128,7 +266,7 @@ fn run_test_suite(tcs: Vec<StateTest>, config: StateTestConfig) -> Result<()> {
     Ok(())
 }
 
-fn run_single_test(test: StateTest, mut config: StateTestConfig) -> Result<()> {
+fn run_single_test(test: StateTest, mut config: StateTestConfig, dump_state: bool) -> Result<()> {
     println!("{}", &test);
 
     fn kv(storage: std::collections::HashMap<U256, U256>) -> Vec<String> {
@@ -182,7 +320,13 @@ fn run_single_test(test: StateTest, mut config: StateTestConfig) -> Result<()> {
     println!("FAILED: {:?}", trace.failed);
     println!("GAS: {:?}", trace.gas);
     table.printstd();
-    println!("result={:?}", test.run(config));
+
+    let result = test.clone().run(config.clone());
+    println!("result={:?}", result);
+
+    if dump_state && result.is_err() {
+        dump_state::print_post_state_diff(&test, &config)?;
+    }
 
     Ok(())
 }
@@ -235,6 +379,11 @@ fn main() -> Result<()> {
         ..Default::default()
     };
 
+    if let Some(Command::Fuzz { iterations, seed }) = args.command {
+        fuzz::run_fuzz(iterations, seed, bytecode_test_config)?;
+        return Ok(());
+    }
+
     if let Some(raw) = &args.raw {
         run_bytecode(&raw, bytecode_test_config)?;
         return Ok(());
@@ -242,6 +391,8 @@ fn main() -> Result<()> {
 
     ResultCache::new(PathBuf::from(RESULT_CACHE))?.sort()?;
 
+    let skip_manifest = SkipManifest::load(&PathBuf::from(&args.skip_manifest))?;
+
     let config = StateTestConfig {
         max_gas: Gas(1000000),
         run_circuit: !args.skip_circuit,
@@ -250,25 +401,44 @@ fn main() -> Result<()> {
 
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
-    let files = glob::glob(&format!("{}/*.yml", args.path))
+    let glob_ext = match args.format {
+        TestFormat::Yaml => "yml",
+        TestFormat::Json => "json",
+    };
+    let files = glob::glob(&format!("{}/*.{}", args.path, glob_ext))
         .expect("Failed to read glob pattern")
         .map(|f| f.unwrap())
-        .filter(|f| !FILE_IGNORE_LIST.iter().any(|e| f.as_path().to_string_lossy().contains(e)));
+        .filter(|f| {
+            !matches!(
+                skip_manifest.lookup(&f.as_path().to_string_lossy()),
+                Some(entry) if entry.category != SkipCategory::ExpectedFail
+            )
+        });
 
     let mut tests = Vec::new();
     let mut lllc = Lllc::default().with_docker_lllc().with_default_cache()?;
+    let mut json_builder = JsonStateTestBuilder::new();
 
     log::info!("Parsing and compliling tests...");
     for file in files {
         let src = std::fs::read_to_string(&file)?;
         let path = file.as_path().to_string_lossy();
         println!("======>{}",path);
-        let mut tcs = match YamlStateTestBuilder::new(&mut lllc).from_yaml(&path, &src) {
-            Err(err) => {
-                log::warn!("Failed to load {}: {:?}", path, err);
-                Vec::new()
-            }
-            Ok(tcs) => tcs,
+        let mut tcs = match args.format {
+            TestFormat::Yaml => match YamlStateTestBuilder::new(&mut lllc).from_yaml(&path, &src) {
+                Err(err) => {
+                    log::warn!("Failed to load {}: {:?}", path, err);
+                    Vec::new()
+                }
+                Ok(tcs) => tcs,
+            },
+            TestFormat::Json => match json_builder.from_json(&path, &src) {
+                Err(err) => {
+                    log::warn!("Failed to load {}: {:?}", path, err);
+                    Vec::new()
+                }
+                Ok(tcs) => tcs,
+            },
         };
         tests.append(&mut tcs);
     }
@@ -279,9 +449,20 @@ fn main() -> Result<()> {
             bail!("test '{}' not found", test_id);
         }
         let test = tests.remove(0);
-        run_single_test(test, config)?;
+        run_single_test(test, config, args.dump_state)?;
     } else {
-        run_test_suite(tests, config)?;
+        let coverage = args.coverage.then(Coverage::new);
+        run_test_suite(
+            tests,
+            config,
+            args.fork.as_deref(),
+            coverage.as_ref(),
+            &skip_manifest,
+        )?;
+        if let Some(coverage) = &coverage {
+            coverage.print_summary();
+            coverage.write_json(&PathBuf::from(COVERAGE_FILE))?;
+        }
     }
 
     Ok(())
@@ -0,0 +1,61 @@
+//! Structured skip/expected-failure manifest, replacing the old
+//! compile-time `TEST_IGNORE_LIST`/`FILE_IGNORE_LIST` arrays with an
+//! auditable, documented `skip.toml` loaded at startup.
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Why a test/file pattern is listed in the manifest.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipCategory {
+    /// We don't implement the feature this test exercises yet.
+    Unsupported,
+    /// We should pass this but don't; tracked as a bug rather than ignored
+    /// outright.
+    KnownBug,
+    /// Passes, but too slow to run by default.
+    Slow,
+    /// We *expect* this test to fail; its outcome is inverted so an
+    /// unexpected pass is reported as an error instead of silently rotting.
+    ExpectedFail,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct SkipEntry {
+    /// A test id or a glob matched against the source file path.
+    pub pattern: String,
+    pub category: SkipCategory,
+    pub reason: String,
+}
+
+/// Parsed `skip.toml`: `[[skip]] pattern = "..." category = "..." reason = "..."`.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct SkipManifest {
+    #[serde(rename = "skip", default)]
+    entries: Vec<SkipEntry>,
+}
+
+impl SkipManifest {
+    /// Loads the manifest from `path`, or returns an empty one if the file
+    /// doesn't exist (skips are opt-in, not required).
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&raw)?)
+    }
+
+    /// Finds the entry whose pattern matches `test_id`, either as an exact
+    /// id or as a glob (so e.g. `stExample*` covers a whole Filler/JSON
+    /// source's worth of generated ids).
+    pub fn lookup(&self, test_id: &str) -> Option<&SkipEntry> {
+        self.entries.iter().find(|entry| {
+            entry.pattern == test_id
+                || glob::Pattern::new(&entry.pattern)
+                    .map(|p| p.matches(test_id))
+                    .unwrap_or(false)
+        })
+    }
+}
@@ -0,0 +1,64 @@
+//! Generates `IMMEDIATE_SIZE`/`IS_PUSH` opcode tables from `instructions.in`
+//! so that push/immediate widths live in one declarative spec instead of
+//! being scattered across `get_push_size`/`is_push` and any in-circuit
+//! lookup derived from them.
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let spec_path = "instructions.in";
+    println!("cargo:rerun-if-changed={}", spec_path);
+
+    let spec = fs::read_to_string(spec_path).expect("failed to read instructions.in");
+
+    // `0xff` marks a variable-length immediate (currently only RJUMPV);
+    // callers that hit it fall back to runtime-computed sizing.
+    let mut immediate_size = [0u8; 256];
+    let mut is_push = [false; 256];
+
+    for line in spec.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let opcode_hex = fields.next().expect("missing opcode field");
+        let _name = fields.next().expect("missing name field");
+        let size_field = fields.next().expect("missing size field");
+        let is_push_opcode = fields.next() == Some("push");
+
+        let opcode = u8::from_str_radix(
+            opcode_hex
+                .strip_prefix("0x")
+                .expect("opcode must be hex, e.g. 0x60"),
+            16,
+        )
+        .expect("invalid opcode hex");
+
+        immediate_size[opcode as usize] = if size_field == "var" {
+            0xff
+        } else {
+            size_field.parse().expect("invalid immediate size")
+        };
+        is_push[opcode as usize] = is_push_opcode;
+    }
+
+    let mut generated = String::new();
+    generated.push_str("pub(crate) const IMMEDIATE_SIZE: [u8; 256] = [\n");
+    for byte in immediate_size {
+        generated.push_str(&format!("    {},\n", byte));
+    }
+    generated.push_str("];\n\n");
+
+    generated.push_str("pub(crate) const IS_PUSH: [bool; 256] = [\n");
+    for flag in is_push {
+        generated.push_str(&format!("    {},\n", flag));
+    }
+    generated.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    fs::write(Path::new(&out_dir).join("opcode_tables.rs"), generated)
+        .expect("failed to write opcode_tables.rs");
+}
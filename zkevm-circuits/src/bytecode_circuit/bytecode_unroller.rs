@@ -18,6 +18,7 @@ use halo2_proofs::{
     poly::Rotation,
 };
 use keccak256::plain::Keccak;
+use rayon::prelude::*;
 use std::vec;
 
 use super::param::PUSH_TABLE_WIDTH;
@@ -31,6 +32,23 @@ pub(crate) struct BytecodeRow<F: Field> {
     value: F,
 }
 
+/// A single bytecode row with every accumulator (`value_rlc`,
+/// `push_data_left`, the `code_hash` RLC) already resolved, produced by
+/// [`BytecodeCircuitConfig::compute_row_groups`] so that assignment only has
+/// to copy values into cells.
+#[derive(Clone, Debug)]
+struct AssignedBytecodeRow<F: Field> {
+    code_hash: Value<F>,
+    tag: F,
+    index: F,
+    is_code: F,
+    value: F,
+    push_data_left: u64,
+    value_rlc: Value<F>,
+    length: F,
+    push_data_size: F,
+}
+
 /// Unrolled bytecode
 #[derive(Clone, Debug, PartialEq)]
 pub struct UnrolledBytecode<F: Field> {
@@ -53,6 +71,12 @@ pub struct BytecodeCircuitConfig<F> {
     push_data_left_inv: Column<Advice>,
     push_data_left_is_zero: IsZeroConfig<F>,
     push_table: [Column<Fixed>; PUSH_TABLE_WIDTH],
+    length_inv: Column<Advice>,
+    length_is_zero: IsZeroConfig<F>,
+    // Single-row auxiliary table holding the RLC of the empty Keccak digest,
+    // computed at synthesis time since it depends on the `evm_word` challenge.
+    empty_hash_table_is_enabled: Column<Fixed>,
+    empty_hash_table: Column<Advice>,
     // External tables
     pub(crate) keccak_table: KeccakTable,
 }
@@ -89,6 +113,9 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
         let push_data_size = meta.advice_column();
         let push_data_left_inv = meta.advice_column();
         let push_table = array_init::array_init(|_| meta.fixed_column());
+        let length_inv = meta.advice_column();
+        let empty_hash_table_is_enabled = meta.fixed_column();
+        let empty_hash_table = meta.advice_column_in(SecondPhase);
 
         let is_header_to_header = |meta: &mut VirtualCells<F>| {
             and::expr(vec![
@@ -134,6 +161,14 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
             push_data_left_inv,
         );
 
+        // A header row is for an empty bytecode when `length == 0`.
+        let length_is_zero = IsZeroChip::configure(
+            meta,
+            |meta| meta.query_fixed(q_enable, Rotation::cur()),
+            |meta| meta.query_advice(length, Rotation::cur()),
+            length_inv,
+        );
+
         // When q_first || q_last ->
         // assert cur.tag == Header
         meta.create_gate("first and last row", |meta| {
@@ -229,17 +264,6 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
                 meta.query_advice(length, Rotation::cur()),
             );
 
-            // TODO: assert cur.hash == EMPTY_HASH
-            // FIXME: Since randomness is only known at synthesis time, the RLC of empty
-            // code_hash is not constant.  Consider doing a lookup to the empty code_hash
-            // value? cb.condition(length_is_zero.clone().is_zero_expression,
-            // |cb| {     cb.require_equal(
-            //         "if length == 0: code_hash == RLC(EMPTY_HASH, randomness)",
-            //         meta.query_advice(bytecode_table.code_hash, Rotation::cur()),
-            //         Expression::Constant(keccak(&[], randomness)),
-            //     );
-            // });
-
             cb.gate(and::expr(vec![
                 meta.query_fixed(q_enable, Rotation::cur()),
                 or::expr(vec![
@@ -249,6 +273,35 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
             ]))
         });
 
+        // When is_header && length == 0 ->
+        // assert cur.hash == RLC(EMPTY_HASH, evm_word challenge)
+        //
+        // The RLC of the empty Keccak digest isn't known until synthesis
+        // (it depends on the `evm_word` challenge), so it can't be asserted
+        // as a gate constant. Instead it's looked up against a one-row
+        // fixed auxiliary table populated in `load_aux_tables`, the same way
+        // `push_table` backs the push-data-size gate above. This covers both
+        // genuine empty-bytecode headers and the padding rows emitted by
+        // `set_padding_row`.
+        meta.lookup_any("empty_code_hash_lookup(cur.hash)", |meta| {
+            let enable = and::expr(vec![
+                meta.query_fixed(q_enable, Rotation::cur()),
+                is_header(meta),
+                length_is_zero.clone().is_zero_expression,
+            ]);
+
+            vec![
+                (
+                    enable.clone(),
+                    meta.query_fixed(empty_hash_table_is_enabled, Rotation::cur()),
+                ),
+                (
+                    enable * meta.query_advice(bytecode_table.code_hash, Rotation::cur()),
+                    meta.query_advice(empty_hash_table, Rotation::cur()),
+                ),
+            ]
+        });
+
         // When is_header_to_byte ->
         // assert next.length == cur.length
         // assert next.index == 0
@@ -409,6 +462,10 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
             push_data_left_inv,
             push_data_left_is_zero,
             push_table,
+            length_inv,
+            length_is_zero,
+            empty_hash_table_is_enabled,
+            empty_hash_table,
             keccak_table,
         }
     }
@@ -425,6 +482,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         self.assign_internal(layouter, size, witness, challenges, true)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn assign_internal(
         &self,
         layouter: &mut impl Layouter<F>,
@@ -435,25 +493,86 @@ impl<F: Field> BytecodeCircuitConfig<F> {
     ) -> Result<(), Error> {
         let push_data_left_is_zero_chip =
             IsZeroChip::construct(self.push_data_left_is_zero.clone());
+        let length_is_zero_chip = IsZeroChip::construct(self.length_is_zero.clone());
 
         // Subtract the unusable rows from the size
         assert!(size > self.minimum_rows);
         let last_row_offset = size - self.minimum_rows + 1;
 
+        // Resolve every row's accumulators (value_rlc, push_data_left,
+        // code_hash RLC) up front. Each `UnrolledBytecode`'s
+        // chain only depends on its own bytes and the challenges, so this is
+        // fully parallelizable and keeps the expensive field arithmetic off
+        // the single `assign_region` critical path below.
+        //
+        // Grouped by source bytecode (rather than one flat `Vec`) so the
+        // assignment loop below walks explicit per-bytecode row ranges. This
+        // is as far as the parallelization can go with the `Layouter`/
+        // `Region` API this crate is built against: `set_row`/
+        // `set_padding_row` take `region: &mut Region<'_, F>`, and `Region`
+        // has no API for handing out disjoint mutable sub-ranges to separate
+        // threads, so the actual `assign_advice`/`assign_fixed` calls for
+        // different bytecodes cannot run concurrently inside one region.
+        // Grouping by range here means that work only has to be reordered,
+        // not redesigned, if a future `halo2` version exposes a
+        // parallel-region-assignment API.
+        let row_groups = Self::compute_row_groups(witness, challenges);
+        let total_rows: usize = row_groups.iter().map(|group| group.len()).sum();
+
+        // Padding rows are headers with `length == 0`, so `empty_code_hash_lookup`
+        // requires their `hash` to equal the RLC of `keccak("")` — the same value
+        // `load_aux_tables` populates the empty-hash table with, not zero.
+        let empty_hash_rlc = challenges.evm_word().map(|challenge| {
+            RandomLinearCombination::<F, 32>::random_linear_combine(
+                keccak(&[]).to_le_bytes(),
+                challenge,
+            )
+        });
+
+        if fail_fast && total_rows > last_row_offset + 1 {
+            log::error!(
+                "Bytecode Circuit: rows.len()={} > last_row_offset + 1={}",
+                total_rows,
+                last_row_offset + 1
+            );
+            return Err(Error::Synthesis);
+        }
+
         layouter.assign_region(
             || "assign bytecode",
             |mut region| {
                 let mut offset = 0;
-                for bytecode in witness.iter() {
-                    self.assign_bytecode(
-                        &mut region,
-                        bytecode,
-                        challenges,
-                        &push_data_left_is_zero_chip,
-                        &mut offset,
-                        last_row_offset,
-                        fail_fast,
-                    )?;
+                // Walk one bytecode's row range at a time (rather than one
+                // flat `Vec`) so the boundary each bytecode occupies in the
+                // region is explicit. The calls inside each range still run
+                // sequentially on this thread: `set_row` takes `&mut Region`,
+                // and `Region` offers no way to split itself into disjoint
+                // mutable sub-ranges for other threads to assign into
+                // concurrently.
+                'outer: for group in &row_groups {
+                    for row in group {
+                        if offset > last_row_offset {
+                            break 'outer;
+                        }
+                        self.set_row(
+                            &mut region,
+                            &push_data_left_is_zero_chip,
+                            &length_is_zero_chip,
+                            offset,
+                            true,
+                            offset == last_row_offset,
+                            row.code_hash,
+                            row.tag,
+                            row.index,
+                            row.is_code,
+                            row.value,
+                            row.push_data_left,
+                            row.value_rlc,
+                            row.length,
+                            row.push_data_size,
+                        )?;
+                        offset += 1;
+                    }
                 }
 
                 // Padding
@@ -461,8 +580,10 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                     self.set_padding_row(
                         &mut region,
                         &push_data_left_is_zero_chip,
+                        &length_is_zero_chip,
                         idx,
                         last_row_offset,
+                        empty_hash_rlc,
                     )?;
                 }
                 Ok(())
@@ -470,101 +591,149 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         )
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn assign_bytecode(
-        &self,
-        region: &mut Region<'_, F>,
+    /// Computes the fully-resolved rows for every bytecode in `witness`, kept
+    /// as one `Vec` of rows per bytecode (rather than flattened) so callers
+    /// can see each bytecode's row range explicitly. Bytecodes are mapped in
+    /// parallel with rayon; the grouping itself stays in `witness`'s input
+    /// order regardless of scheduling.
+    fn compute_row_groups(
+        witness: &[UnrolledBytecode<F>],
+        challenges: &Challenges<Value<F>>,
+    ) -> Vec<Vec<AssignedBytecodeRow<F>>> {
+        witness
+            .par_iter()
+            .map(|bytecode| Self::compute_bytecode_rows(bytecode, challenges))
+            .collect()
+    }
+
+    /// Derives `(push_data_left, push_data_size)` for every row of an EOF
+    /// container from the `is_code` sequence `unroll_eof_body` already
+    /// resolved, rather than re-deriving immediate sizes from byte values
+    /// (which would require re-running the same section-aware scan here).
+    /// Scanning back-to-front, a run of `is_code == false` rows of length
+    /// `L` gets `push_data_left` counting down from `L` to `1`, and every
+    /// `is_code` row gets `0` with `push_data_size` set to the run that
+    /// follows it.
+    fn eof_push_data_left(rows: &[BytecodeRow<F>]) -> Vec<(u64, u64)> {
+        let mut out = vec![(0u64, 0u64); rows.len()];
+        for idx in (0..rows.len()).rev() {
+            let is_code = rows[idx].is_code == F::one();
+            let next_left = if idx + 1 < rows.len() { out[idx + 1].0 } else { 0 };
+            out[idx].0 = if is_code { 0 } else { 1 + next_left };
+        }
+        for idx in 0..rows.len() {
+            let is_code = rows[idx].is_code == F::one();
+            out[idx].1 = if is_code && idx + 1 < rows.len() {
+                out[idx + 1].0
+            } else {
+                0
+            };
+        }
+        // Row 0 is always the header (never itself part of the push/immediate
+        // tracking), matching `compute_bytecode_rows`'s untouched
+        // `push_data_left`/`push_data_size` for `idx == 0` on the legacy path.
+        if let Some(header) = out.first_mut() {
+            *header = (0, 0);
+        }
+        out
+    }
+
+    /// Pure computation of one bytecode's rows: no region/layouter access,
+    /// so it can run off the main thread.
+    fn compute_bytecode_rows(
         bytecode: &UnrolledBytecode<F>,
         challenges: &Challenges<Value<F>>,
-        push_rindex_is_zero_chip: &IsZeroChip<F>,
-        offset: &mut usize,
-        last_row_offset: usize,
-        fail_fast: bool,
-    ) -> Result<(), Error> {
-        // Run over all the bytes
+    ) -> Vec<AssignedBytecodeRow<F>> {
+        // `unroll_eof_body` already resolved `is_code` (and implicitly every
+        // immediate's size) using the section-aware `eof_immediate_size`;
+        // recomputing it here via the legacy `get_push_size` (keyed only on
+        // PUSH opcode byte values) would disagree with that from the very
+        // first byte (`0xEF`, never a PUSH opcode). So for EOF containers we
+        // derive `push_data_left`/`push_data_size` from the already-resolved
+        // `is_code` sequence instead of re-deriving immediate sizes from byte
+        // values.
+        let is_eof = bytecode.bytes.starts_with(&EOF_MAGIC);
+        let eof_push_data = is_eof.then(|| Self::eof_push_data_left(&bytecode.rows));
+
         let mut push_data_left = 0;
         let mut push_data_size = 0;
         let mut value_rlc = challenges.keccak_input().map(|_| F::zero());
         let length = F::from(bytecode.bytes.len() as u64);
 
-        for (idx, row) in bytecode.rows.iter().enumerate() {
-            if fail_fast && *offset > last_row_offset {
-                log::error!(
-                    "Bytecode Circuit: offset={} > last_row_offset={}",
-                    offset,
-                    last_row_offset
-                );
-                return Err(Error::Synthesis);
-            }
-
-            // TODO: why different code_hash for each row? Is this going to produce the same
-            // result for every row?
-            let code_hash = challenges.evm_word().map(|challenge| {
-                RandomLinearCombination::<F, 32>::random_linear_combine(
-                    row.code_hash.to_le_bytes(),
-                    challenge,
-                )
-            });
-
-            // Track which byte is an opcode and which is push
-            // data
-            if idx > 0 {
-                let is_code = push_data_left == 0;
-                assert_eq!(F::from(is_code as u64), row.is_code, "is_code must match");
-
-                push_data_size = get_push_size(row.value.get_lower_128() as u8);
-
-                push_data_left = if is_code {
-                    push_data_size
-                } else {
-                    push_data_left - 1
-                };
+        bytecode
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| {
+                // TODO: why different code_hash for each row? Is this going to produce the
+                // same result for every row?
+                let code_hash = challenges.evm_word().map(|challenge| {
+                    RandomLinearCombination::<F, 32>::random_linear_combine(
+                        row.code_hash.to_le_bytes(),
+                        challenge,
+                    )
+                });
 
-                value_rlc
-                    .as_mut()
-                    .zip(challenges.keccak_input())
-                    .map(|(value_rlc, challenge)| *value_rlc = *value_rlc * challenge + row.value);
-            }
+                if let Some(eof_push_data) = &eof_push_data {
+                    push_data_left = eof_push_data[idx].0;
+                    push_data_size = eof_push_data[idx].1;
+                    if idx > 0 {
+                        value_rlc.as_mut().zip(challenges.keccak_input()).map(
+                            |(value_rlc, challenge)| {
+                                *value_rlc = *value_rlc * challenge + row.value
+                            },
+                        );
+                    }
+                } else if idx > 0 {
+                    // Track which byte is an opcode and which is push data
+                    let is_code = push_data_left == 0;
+                    assert_eq!(F::from(is_code as u64), row.is_code, "is_code must match");
+
+                    push_data_size = get_push_size(row.value.get_lower_128() as u8);
+
+                    push_data_left = if is_code {
+                        push_data_size
+                    } else {
+                        push_data_left - 1
+                    };
+
+                    value_rlc.as_mut().zip(challenges.keccak_input()).map(
+                        |(value_rlc, challenge)| *value_rlc = *value_rlc * challenge + row.value,
+                    );
+                }
 
-            // Set the data for this row
-            if *offset <= last_row_offset {
-                self.set_row(
-                    region,
-                    push_rindex_is_zero_chip,
-                    *offset,
-                    true,
-                    *offset == last_row_offset,
+                AssignedBytecodeRow {
                     code_hash,
-                    row.tag,
-                    row.index,
-                    row.is_code,
-                    row.value,
+                    tag: row.tag,
+                    index: row.index,
+                    is_code: row.is_code,
+                    value: row.value,
                     push_data_left,
                     value_rlc,
                     length,
-                    F::from(push_data_size as u64),
-                )?;
-                *offset += 1;
-            }
-        }
-
-        Ok(())
+                    push_data_size: F::from(push_data_size as u64),
+                }
+            })
+            .collect()
     }
 
     fn set_padding_row(
         &self,
         region: &mut Region<'_, F>,
         push_data_left_is_zero_chip: &IsZeroChip<F>,
+        length_is_zero_chip: &IsZeroChip<F>,
         offset: usize,
         last_row_offset: usize,
+        empty_hash_rlc: Value<F>,
     ) -> Result<(), Error> {
         self.set_row(
             region,
             push_data_left_is_zero_chip,
+            length_is_zero_chip,
             offset,
             offset < last_row_offset,
             offset == last_row_offset,
-            Value::known(F::zero()),
+            empty_hash_rlc,
             F::from(BytecodeFieldTag::Header as u64),
             F::zero(),
             F::zero(),
@@ -581,6 +750,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         &self,
         region: &mut Region<'_, F>,
         push_data_left_is_zero_chip: &IsZeroChip<F>,
+        length_is_zero_chip: &IsZeroChip<F>,
         offset: usize,
         enable: bool,
         last: bool,
@@ -658,11 +828,17 @@ impl<F: Field> BytecodeCircuitConfig<F> {
             Value::known(F::from(push_data_left)),
         )?;
 
+        length_is_zero_chip.assign(region, offset, Value::known(length))?;
+
         Ok(())
     }
 
     /// load fixed tables
-    pub(crate) fn load_aux_tables(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+    pub(crate) fn load_aux_tables(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
         // push table: BYTE -> NUM_PUSHED:
         // [0, OpcodeId::PUSH1] -> 0
         // [OpcodeId::PUSH1, OpcodeId::PUSH32] -> [1..32]
@@ -688,11 +864,51 @@ impl<F: Field> BytecodeCircuitConfig<F> {
             },
         )?;
 
+        // Empty-code-hash table: a single row holding the RLC of the empty
+        // Keccak digest, computed here (rather than baked into the gate)
+        // since the `evm_word` challenge is only known at synthesis time.
+        let empty_hash_rlc = challenges.evm_word().map(|challenge| {
+            RandomLinearCombination::<F, 32>::random_linear_combine(
+                keccak(&[]).to_le_bytes(),
+                challenge,
+            )
+        });
+        layouter.assign_region(
+            || "empty hash table",
+            |mut region| {
+                region.assign_fixed(
+                    || "empty hash table is_enabled",
+                    self.empty_hash_table_is_enabled,
+                    0,
+                    || Value::known(F::one()),
+                )?;
+                region.assign_advice(
+                    || "empty hash table value",
+                    self.empty_hash_table,
+                    0,
+                    || empty_hash_rlc,
+                )?;
+                Ok(())
+            },
+        )?;
+
         Ok(())
     }
 }
 
-/// Get unrolled bytecode from raw bytes
+/// Magic bytes identifying an EOF container (EIP-3540).
+const EOF_MAGIC: [u8; 2] = [0xef, 0x00];
+
+/// EOF header section kinds (EIP-3540/3671).
+const EOF_SECTION_TYPE: u8 = 0x01;
+const EOF_SECTION_CODE: u8 = 0x02;
+const EOF_SECTION_CONTAINER: u8 = 0x03;
+const EOF_SECTION_DATA: u8 = 0x04;
+const EOF_SECTION_TERMINATOR: u8 = 0x00;
+
+/// Get unrolled bytecode from raw bytes. Detects EIP-3540 EOF containers by
+/// their `0xEF00` magic and unrolls them section-aware (EIP-3670/4200);
+/// anything else is treated as flat legacy code.
 pub fn unroll<F: Field>(bytes: Vec<u8>) -> UnrolledBytecode<F> {
     let code_hash = keccak(&bytes[..]);
     let mut rows = vec![BytecodeRow::<F> {
@@ -702,7 +918,19 @@ pub fn unroll<F: Field>(bytes: Vec<u8>) -> UnrolledBytecode<F> {
         is_code: F::zero(),
         value: F::from(bytes.len() as u64),
     }];
-    // Run over all the bytes
+
+    if bytes.starts_with(&EOF_MAGIC) {
+        unroll_eof_body(&bytes, code_hash, &mut rows);
+    } else {
+        unroll_legacy_body(&bytes, code_hash, &mut rows);
+    }
+
+    UnrolledBytecode { bytes, rows }
+}
+
+/// Unrolls a flat legacy code stream, tracking PUSH immediates via
+/// `get_push_size`.
+fn unroll_legacy_body<F: Field>(bytes: &[u8], code_hash: Word, rows: &mut Vec<BytecodeRow<F>>) {
     let mut push_rindex = 0;
     for (index, byte) in bytes.iter().enumerate() {
         // Track which byte is an opcode and which is push data
@@ -721,27 +949,237 @@ pub fn unroll<F: Field>(bytes: Vec<u8>) -> UnrolledBytecode<F> {
             value: F::from(*byte as u64),
         });
     }
-    UnrolledBytecode { bytes, rows }
 }
 
+/// Unrolls an EOF container: the header (magic, version, section headers) and
+/// the type-section body are never code, each code section is scanned with `eof_immediate_size` in
+/// place of `get_push_size` so that RJUMP/RJUMPI/RJUMPV/CALLF/JUMPF/
+/// DATALOADN immediates aren't mistaken for opcodes, and data sections are
+/// always non-code.
+fn unroll_eof_body<F: Field>(bytes: &[u8], code_hash: Word, rows: &mut Vec<BytecodeRow<F>>) {
+    let mut push_row = |index: usize, value: u8, is_code: bool| {
+        rows.push(BytecodeRow::<F> {
+            code_hash,
+            tag: F::from(BytecodeFieldTag::Byte as u64),
+            index: F::from(index as u64),
+            is_code: F::from(is_code as u64),
+            value: F::from(value as u64),
+        });
+    };
+
+    // magic (2 bytes) + version (1 byte)
+    let mut cursor = 0usize;
+    for _ in 0..3.min(bytes.len()) {
+        push_row(cursor, bytes[cursor], false);
+        cursor += 1;
+    }
+
+    // Section headers: kind (1 byte) + big-endian u16 size, terminated by 0x00.
+    let mut type_section_size = 0usize;
+    let mut code_section_sizes = Vec::new();
+    let mut data_section_size = 0usize;
+    while cursor < bytes.len() {
+        let kind = bytes[cursor];
+        push_row(cursor, kind, false);
+        cursor += 1;
+
+        if kind == EOF_SECTION_TERMINATOR {
+            break;
+        }
+
+        if cursor + 1 >= bytes.len() {
+            break;
+        }
+        let size = u16::from_be_bytes([bytes[cursor], bytes[cursor + 1]]) as usize;
+        push_row(cursor, bytes[cursor], false);
+        push_row(cursor + 1, bytes[cursor + 1], false);
+        cursor += 2;
+
+        match kind {
+            EOF_SECTION_TYPE => type_section_size = size,
+            EOF_SECTION_CONTAINER => {}
+            EOF_SECTION_CODE => code_section_sizes.push(size),
+            EOF_SECTION_DATA => data_section_size = size,
+            _ => {}
+        }
+    }
+
+    // Type section body (per-code-section metadata) precedes the code
+    // sections and is never code.
+    let type_end = (cursor + type_section_size).min(bytes.len());
+    while cursor < type_end {
+        push_row(cursor, bytes[cursor], false);
+        cursor += 1;
+    }
+
+    // Code sections: each opcode's fixed-size immediate is non-code, exactly
+    // like legacy PUSH data.
+    for size in code_section_sizes {
+        let section_end = (cursor + size).min(bytes.len());
+        let mut immediate_rindex = 0u64;
+        while cursor < section_end {
+            let byte = bytes[cursor];
+            let is_code = immediate_rindex == 0;
+            immediate_rindex = if is_code {
+                eof_immediate_size(byte, bytes.get(cursor + 1).copied())
+            } else {
+                immediate_rindex - 1
+            };
+            push_row(cursor, byte, is_code);
+            cursor += 1;
+        }
+    }
+
+    // Data section: never code.
+    let data_end = (cursor + data_section_size).min(bytes.len());
+    while cursor < data_end {
+        push_row(cursor, bytes[cursor], false);
+        cursor += 1;
+    }
+
+    // Anything left over (e.g. a container section we don't special-case)
+    // is conservatively treated as non-code.
+    while cursor < bytes.len() {
+        push_row(cursor, bytes[cursor], false);
+        cursor += 1;
+    }
+}
+
+// `IMMEDIATE_SIZE`/`IS_PUSH`, generated by `build.rs` from `instructions.in`:
+// the single source of truth for how many immediate bytes follow each
+// opcode. `IMMEDIATE_SIZE[RJUMPV] == 0xff` marks the one variable-length
+// immediate, handled specially in `eof_immediate_size`.
+include!(concat!(env!("OUT_DIR"), "/opcode_tables.rs"));
+
+const RJUMPV: u8 = 0x5e;
+const VARIABLE_IMMEDIATE_SIZE: u8 = 0xff;
+
 fn is_push(byte: u8) -> bool {
-    OpcodeId::from(byte).is_push()
+    IS_PUSH[byte as usize]
 }
 
 fn get_push_size(byte: u8) -> u64 {
     if is_push(byte) {
-        byte as u64 - OpcodeId::PUSH1.as_u64() + 1
+        IMMEDIATE_SIZE[byte as usize] as u64
     } else {
         0u64
     }
 }
 
+/// Size, in bytes, of the immediate operand following an EOF code-section
+/// opcode. `next_byte`, when present, is the byte right after `opcode` and is
+/// only consulted for `RJUMPV`, whose immediate count byte determines how
+/// many 2-byte jump-table entries follow.
+fn eof_immediate_size(opcode: u8, next_byte: Option<u8>) -> u64 {
+    match IMMEDIATE_SIZE[opcode as usize] {
+        VARIABLE_IMMEDIATE_SIZE if opcode == RJUMPV => 1 + 2 * next_byte.unwrap_or(0) as u64,
+        size => size as u64,
+    }
+}
+
 fn keccak(msg: &[u8]) -> Word {
     let mut keccak = Keccak::default();
     keccak.update(msg);
     Word::from_big_endian(keccak.digest().as_slice())
 }
 
+/// Storage backend for cached [`UnrolledBytecode`] serializations, keyed by
+/// code hash. Implementations can back this with a file, a KV store, etc.
+pub trait CacheBackend {
+    /// Looks up a previously-stored serialization for `code_hash`.
+    fn get(&mut self, code_hash: &Word) -> Option<Vec<u8>>;
+    /// Stores a serialization for `code_hash`.
+    fn put(&mut self, code_hash: Word, bytes: Vec<u8>);
+}
+
+/// Unrolls `bytes`, first checking `cache` by code hash so that repeatedly
+/// proving the same deployed contract across blocks/tests can skip the
+/// byte scan `unroll` would otherwise redo every time. The Keccak digest is
+/// still computed on every call, as the cache key.
+pub fn unroll_cached<F: Field>(bytes: Vec<u8>, cache: &mut impl CacheBackend) -> UnrolledBytecode<F> {
+    let code_hash = keccak(&bytes[..]);
+    if let Some(cached) = cache.get(&code_hash) {
+        return UnrolledBytecode::from_bytes(&cached);
+    }
+    let unrolled = unroll(bytes);
+    cache.put(code_hash, unrolled.to_bytes());
+    unrolled
+}
+
+fn write_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn read_u64(buf: &mut &[u8]) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[..8]);
+    *buf = &buf[8..];
+    u64::from_le_bytes(bytes)
+}
+
+impl<F: Field> BytecodeRow<F> {
+    fn write_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.code_hash.to_le_bytes());
+        for value in [self.tag, self.index, self.is_code, self.value] {
+            out.extend_from_slice(value.to_repr().as_ref());
+        }
+    }
+
+    fn read_from(buf: &mut &[u8]) -> Self {
+        let mut hash_bytes = [0u8; 32];
+        hash_bytes.copy_from_slice(&buf[..32]);
+        *buf = &buf[32..];
+        let code_hash = Word::from_little_endian(&hash_bytes);
+
+        let mut read_field = || -> F {
+            let mut repr = F::Repr::default();
+            let len = repr.as_ref().len();
+            repr.as_mut().copy_from_slice(&buf[..len]);
+            *buf = &buf[len..];
+            F::from_repr(repr).expect("corrupt cached bytecode row")
+        };
+
+        BytecodeRow {
+            code_hash,
+            tag: read_field(),
+            index: read_field(),
+            is_code: read_field(),
+            value: read_field(),
+        }
+    }
+}
+
+impl<F: Field> UnrolledBytecode<F> {
+    /// Serializes to a compact binary form for [`CacheBackend`]: the raw
+    /// bytes plus every row's already-resolved field elements, so loading it
+    /// back skips `unroll` entirely.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_u64(&mut out, self.bytes.len() as u64);
+        out.extend_from_slice(&self.bytes);
+        write_u64(&mut out, self.rows.len() as u64);
+        for row in &self.rows {
+            row.write_to(&mut out);
+        }
+        out
+    }
+
+    /// Inverse of [`UnrolledBytecode::to_bytes`].
+    pub fn from_bytes(buf: &[u8]) -> Self {
+        let mut buf = buf;
+        let bytes_len = read_u64(&mut buf) as usize;
+        let bytes = buf[..bytes_len].to_vec();
+        buf = &buf[bytes_len..];
+
+        let rows_len = read_u64(&mut buf) as usize;
+        let rows = (0..rows_len)
+            .map(|_| BytecodeRow::read_from(&mut buf))
+            .collect();
+
+        UnrolledBytecode { bytes, rows }
+    }
+}
+
 fn into_words(message: &[u8]) -> Vec<u64> {
     let words_total = message.len() / 8;
     let mut words: Vec<u64> = vec![0; words_total];
@@ -770,13 +1208,34 @@ impl<F: Field> BytecodeCircuit<F> {
         BytecodeCircuit { bytecodes, size }
     }
 
-    /// Creates bytecode circuit from block and bytecode_size.
+    /// Creates bytecode circuit from block and bytecode_size, unrolling
+    /// every bytecode with rayon's `par_iter`.
     pub fn new_from_block_sized(block: &witness::Block<F>, bytecode_size: usize) -> Self {
-        let bytecodes: Vec<UnrolledBytecode<F>> = block
-            .bytecodes
-            .iter()
-            .map(|(_, b)| unroll(b.bytes.clone()))
-            .collect();
+        Self::new_from_block_sized_with_parallelism(block, bytecode_size, true)
+    }
+
+    /// Same as [`Self::new_from_block_sized`], but `parallel` picks between
+    /// rayon's `par_iter` and a plain sequential `iter` for the per-contract
+    /// unroll step, rather than relying on the `RAYON_NUM_THREADS=1`
+    /// environment variable to force a deterministic single-threaded run.
+    pub fn new_from_block_sized_with_parallelism(
+        block: &witness::Block<F>,
+        bytecode_size: usize,
+        parallel: bool,
+    ) -> Self {
+        let bytecodes: Vec<UnrolledBytecode<F>> = if parallel {
+            block
+                .bytecodes
+                .par_iter()
+                .map(|(_, b)| unroll(b.bytes.clone()))
+                .collect()
+        } else {
+            block
+                .bytecodes
+                .iter()
+                .map(|(_, b)| unroll(b.bytes.clone()))
+                .collect()
+        };
         Self::new(bytecodes, bytecode_size)
     }
 }
@@ -812,7 +1271,7 @@ impl<F: Field> SubCircuit<F> for BytecodeCircuit<F> {
         challenges: &Challenges<Value<F>>,
         layouter: &mut impl Layouter<F>,
     ) -> Result<(), Error> {
-        config.load_aux_tables(layouter)?;
+        config.load_aux_tables(layouter, challenges)?;
         config.assign_internal(layouter, self.size, &self.bytecodes, challenges, false)
     }
 }
@@ -1063,4 +1522,106 @@ mod tests {
             test_bytecode_circuit_unrolled::<Fr>(k, vec![invalid], false);
         }
     }
+
+    /// Builds a minimal single-code-section EOF container (magic, version,
+    /// type/code/data section headers, terminator, a 4-byte type section
+    /// body, one code section holding `code`, an empty data section) so
+    /// EOF-specific opcodes can be exercised without a full EOF validator.
+    fn eof_container(code: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0xef, 0x00, 0x01];
+        bytes.extend_from_slice(&[EOF_SECTION_TYPE, 0x00, 0x04]);
+        bytes.extend_from_slice(&[EOF_SECTION_CODE, 0x00, code.len() as u8]);
+        bytes.extend_from_slice(&[EOF_SECTION_DATA, 0x00, 0x00]);
+        bytes.push(EOF_SECTION_TERMINATOR);
+        // Type entry for code section 0: inputs, outputs (non-returning),
+        // max stack height.
+        bytes.extend_from_slice(&[0x00, 0x80, 0x00, 0x00]);
+        bytes.extend_from_slice(code);
+        bytes
+    }
+
+    /// Each of RJUMP/RJUMPI/RJUMPV/CALLF/JUMPF/DATALOADN's immediate bytes
+    /// must unroll as non-code, and `compute_bytecode_rows` (used by
+    /// `test_bytecode_circuit_unrolled`) must agree with that instead of
+    /// recomputing `is_code` via the legacy `get_push_size` table, which
+    /// knows nothing about these opcodes.
+    #[test]
+    fn eof_opcode_immediates() {
+        let k = 12;
+        const RJUMP: u8 = 0x5c;
+        const RJUMPI: u8 = 0x5d;
+        const DATALOADN: u8 = 0xd1;
+        const CALLF: u8 = 0xe3;
+        const JUMPF: u8 = 0xe5;
+
+        let cases: Vec<Vec<u8>> = vec![
+            vec![RJUMP, 0x00, 0x00],
+            vec![RJUMPI, 0x00, 0x00],
+            vec![RJUMPV, 0x01, 0x00, 0x00],
+            vec![DATALOADN, 0x00, 0x00],
+            vec![CALLF, 0x00, 0x00],
+            vec![JUMPF, 0x00, 0x00],
+        ];
+
+        for code in cases {
+            let unrolled = unroll::<Fr>(eof_container(&code));
+            test_bytecode_circuit_unrolled::<Fr>(k, vec![unrolled], true);
+        }
+    }
+
+    /// `to_bytes`/`from_bytes` round-trip must reproduce every row exactly,
+    /// since `unroll_cached` trusts `from_bytes` output instead of re-running
+    /// `unroll`.
+    #[test]
+    fn to_bytes_from_bytes_round_trip() {
+        let mut bytecode = Bytecode::default();
+        bytecode.push(32, Word::from_little_endian(&[0xab; 32]));
+        bytecode.write(OpcodeId::STOP.as_u8(), true);
+        let unrolled = unroll::<Fr>(bytecode.to_vec());
+
+        let encoded = unrolled.to_bytes();
+        let decoded = UnrolledBytecode::from_bytes(&encoded);
+
+        assert_eq!(unrolled, decoded);
+    }
+
+    struct MapCacheBackend {
+        map: std::collections::HashMap<Word, Vec<u8>>,
+        hits: usize,
+    }
+
+    impl CacheBackend for MapCacheBackend {
+        fn get(&mut self, code_hash: &Word) -> Option<Vec<u8>> {
+            let cached = self.map.get(code_hash).cloned();
+            if cached.is_some() {
+                self.hits += 1;
+            }
+            cached
+        }
+
+        fn put(&mut self, code_hash: Word, bytes: Vec<u8>) {
+            self.map.insert(code_hash, bytes);
+        }
+    }
+
+    /// A second `unroll_cached` call for the same bytecode must hit the cache
+    /// and return rows identical to a fresh `unroll`, not just *a* cached
+    /// value.
+    #[test]
+    fn unroll_cached_hits_cache() {
+        let bytes = vec![OpcodeId::PUSH1.as_u8(), 0x01, OpcodeId::STOP.as_u8()];
+        let mut cache = MapCacheBackend {
+            map: std::collections::HashMap::new(),
+            hits: 0,
+        };
+
+        let first: UnrolledBytecode<Fr> = unroll_cached(bytes.clone(), &mut cache);
+        assert_eq!(cache.map.len(), 1);
+        assert_eq!(cache.hits, 0);
+        let second: UnrolledBytecode<Fr> = unroll_cached(bytes.clone(), &mut cache);
+        assert_eq!(cache.hits, 1);
+
+        assert_eq!(first, second);
+        assert_eq!(first, unroll(bytes));
+    }
 }
@@ -0,0 +1,109 @@
+//! `wasm-bindgen` entrypoints for proving/verifying the bytecode sub-circuit
+//! in isolation, without pulling in the rest of the block pipeline. Gated
+//! behind the `wasm` feature; wire up with `#[cfg(feature = "wasm")] pub mod
+//! wasm;` in this module's `mod.rs`.
+//!
+//! KZG parameters are accepted pre-serialized rather than generated
+//! in-browser: they depend only on `k` (the circuit size), so the host page
+//! can fetch them once from a static URL and hand them to every call here.
+use eth_types::Field;
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{create_proof, keygen_pk, keygen_vk, verify_proof},
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverSHPLONK, VerifierSHPLONK},
+            strategy::SingleStrategy,
+        },
+    },
+    transcript::{
+        Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+    },
+};
+use rand::rngs::OsRng;
+use wasm_bindgen::prelude::*;
+
+use super::bytecode_unroller::{unroll, BytecodeCircuit};
+use crate::util::SubCircuit;
+
+/// `size` must be the same value the host page used for every proof made
+/// against `params_ser` (typically derived once from `params_ser`'s `k` and
+/// cached alongside it): the circuit's row assignment has to match the fixed
+/// domain size baked into the keys `keygen_vk`/`keygen_pk` derive from
+/// `params`, not whatever happens to be just large enough for one call's
+/// `bytecodes_js`. A `size` too small for `bytecodes_js` is not reported as
+/// an error here: `synthesize_sub` calls `assign_internal` with `fail_fast`
+/// off, so rows past the last usable row are dropped and the assignment no
+/// longer satisfies the circuit's constraints.
+fn bytecode_circuit_from_js(
+    bytecodes_js: JsValue,
+    size: usize,
+) -> Result<BytecodeCircuit<Fr>, JsValue> {
+    let bytecodes: Vec<Vec<u8>> = serde_wasm_bindgen::from_value(bytecodes_js)?;
+    let unrolled = bytecodes.into_iter().map(unroll).collect::<Vec<_>>();
+    Ok(BytecodeCircuit::new(unrolled, size))
+}
+
+/// Proves that `bytecodes_js` (a JS array of byte arrays) is correctly
+/// unrolled and committed to, against the KZG parameters serialized in
+/// `params_ser`. `size` fixes the circuit's row count and must match the
+/// value used when proving/verifying anything else against the same
+/// `params_ser` — see [`bytecode_circuit_from_js`]. Returns the proof bytes.
+#[wasm_bindgen]
+pub fn prove_bytecode(
+    bytecodes_js: JsValue,
+    params_ser: &[u8],
+    size: usize,
+) -> Result<Vec<u8>, JsValue> {
+    let circuit = bytecode_circuit_from_js(bytecodes_js, size)?;
+    let params = ParamsKZG::<Bn256>::read(&mut &params_ser[..])
+        .map_err(|err| JsValue::from_str(&format!("invalid params: {err}")))?;
+
+    let vk = keygen_vk(&params, &circuit)
+        .map_err(|err| JsValue::from_str(&format!("keygen_vk failed: {err}")))?;
+    let pk = keygen_pk(&params, vk, &circuit)
+        .map_err(|err| JsValue::from_str(&format!("keygen_pk failed: {err}")))?;
+
+    let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<_>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[]],
+        OsRng,
+        &mut transcript,
+    )
+    .map_err(|err| JsValue::from_str(&format!("create_proof failed: {err}")))?;
+
+    Ok(transcript.finalize())
+}
+
+/// Verifies a proof produced by [`prove_bytecode`] for the same
+/// `bytecodes_js`, `params_ser`, and `size`.
+#[wasm_bindgen]
+pub fn verify_bytecode(
+    bytecodes_js: JsValue,
+    proof_bytes: &[u8],
+    params_ser: &[u8],
+    size: usize,
+) -> Result<bool, JsValue> {
+    let circuit = bytecode_circuit_from_js(bytecodes_js, size)?;
+    let params = ParamsKZG::<Bn256>::read(&mut &params_ser[..])
+        .map_err(|err| JsValue::from_str(&format!("invalid params: {err}")))?;
+
+    let vk = keygen_vk(&params, &circuit)
+        .map_err(|err| JsValue::from_str(&format!("keygen_vk failed: {err}")))?;
+
+    let mut transcript = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(proof_bytes);
+    let result = verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<_>, _, _, _>(
+        &params,
+        &vk,
+        SingleStrategy::new(&params),
+        &[&[]],
+        &mut transcript,
+    );
+
+    Ok(result.is_ok())
+}